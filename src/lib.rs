@@ -0,0 +1,593 @@
+use image::{DynamicImage, GrayImage, RgbImage};
+use std::fmt::{self, Write};
+
+/// Braille Unicode characters map 2×4 dot cells to code points 0x2800‑0x28FF.
+/// The bits are ordered as:
+/// 0 3
+/// 1 4
+/// 2 5
+/// 6 7
+fn cell_to_char(dots: u8) -> char {
+  // Unicode Braille pattern offset
+  std::char::from_u32(0x2800 + dots as u32).unwrap_or(' ')
+}
+
+/// Decides, per source pixel, whether a braille dot is "on".
+///
+/// A mode is consulted by [`BrailleArt::render`] through [`BrailleArtMode::is_on`]
+/// when it builds the per-pixel dot matrix that is then packed into 2×4 cells,
+/// so adding a new lighting rule is just a new variant rather than a new render
+/// path.
+#[derive(Clone, Debug)]
+pub enum BrailleArtMode {
+  /// Turn a dot on when the pixel luminance is below `threshold` (the
+  /// original behavior of the tool).
+  LumaThreshold(u8),
+  /// Turn a dot on where the Sobel gradient magnitude exceeds `threshold`,
+  /// i.e. on edges rather than dark regions.
+  EdgeDetect(u8),
+  /// Floyd–Steinberg error diffusion applied as a pre-pass before packing.
+  /// The diffusion itself is implemented in `floyd_steinberg`.
+  Dither,
+}
+
+impl Default for BrailleArtMode {
+  fn default() -> Self {
+    BrailleArtMode::LumaThreshold(128)
+  }
+}
+
+impl BrailleArtMode {
+  /// Whether the dot covering `(x, y)` should be lit for this mode.
+  fn is_on(&self, img: &GrayImage, x: u32, y: u32) -> bool {
+    match self {
+      BrailleArtMode::LumaThreshold(threshold) => img.get_pixel(x, y).0[0] < *threshold,
+      BrailleArtMode::EdgeDetect(threshold) => sobel_magnitude(img, x, y) > *threshold as u32,
+      // Dithering needs the whole image in scan order, so the decision is
+      // made in a pre-pass; here we fall back to the default threshold.
+      BrailleArtMode::Dither => img.get_pixel(x, y).0[0] < 128,
+    }
+  }
+}
+
+/// Compute a binarization threshold from `gray` using Otsu's method.
+///
+/// The 256-bin luminance histogram is normalized to probabilities, then the
+/// threshold `t` maximizing the between-class variance
+/// `σ²_b(t) = w0·w1·(μ0−μ1)²` is returned.
+pub fn otsu_threshold(gray: &GrayImage) -> u8 {
+  let mut histogram = [0u32; 256];
+  for pixel in gray.pixels() {
+    histogram[pixel.0[0] as usize] += 1;
+  }
+  let total: u32 = gray.pixels().len() as u32;
+  if total == 0 {
+    return 128;
+  }
+
+  let p: Vec<f64> = histogram.iter().map(|&c| c as f64 / total as f64).collect();
+  let mu_t: f64 = p.iter().enumerate().map(|(i, &pt)| i as f64 * pt).sum();
+
+  let mut w0 = 0.0;
+  let mut mu_cum = 0.0;
+  let mut best_t = 0u8;
+  let mut best_var = -1.0;
+  for (t, &pt) in p.iter().enumerate() {
+    w0 += pt;
+    mu_cum += t as f64 * pt;
+    let w1 = 1.0 - w0;
+    if w0 <= 0.0 || w1 <= 0.0 {
+      continue;
+    }
+    let mu0 = mu_cum / w0;
+    let mu1 = (mu_t - mu_cum) / w1;
+    let var_b = w0 * w1 * (mu0 - mu1) * (mu0 - mu1);
+    if var_b > best_var {
+      best_var = var_b;
+      best_t = t as u8;
+    }
+  }
+  best_t
+}
+
+/// Floyd–Steinberg error diffusion over `gray`, returning the binary dot
+/// matrix (`true` where a dot should be lit, i.e. the pixel quantized to black).
+///
+/// Pixels are visited in row-major order over a mutable `f32` working buffer;
+/// each quantization error is pushed onto the not-yet-visited neighbors with
+/// the classic 7/3/5/1 sixteenths weights.
+fn floyd_steinberg(gray: &GrayImage) -> Vec<Vec<bool>> {
+  let (w, h) = gray.dimensions();
+  let (w, h) = (w as usize, h as usize);
+  let mut buf: Vec<f32> = gray.pixels().map(|p| p.0[0] as f32).collect();
+  let mut matrix = vec![vec![false; w]; h];
+
+  for y in 0..h {
+    for x in 0..w {
+      let old = buf[y * w + x];
+      let new = if old < 128.0 { 0.0 } else { 255.0 };
+      matrix[y][x] = old < 128.0;
+      let err = old - new;
+      let mut spread = |nx: usize, ny: usize, weight: f32| {
+        if nx < w && ny < h {
+          buf[ny * w + nx] += err * weight;
+        }
+      };
+      if x + 1 < w {
+        spread(x + 1, y, 7.0 / 16.0);
+      }
+      if y + 1 < h {
+        if x > 0 {
+          spread(x - 1, y + 1, 3.0 / 16.0);
+        }
+        spread(x, y + 1, 5.0 / 16.0);
+        spread(x + 1, y + 1, 1.0 / 16.0);
+      }
+    }
+  }
+  matrix
+}
+
+/// Sobel gradient magnitude at `(x, y)`, clamped to the image bounds.
+fn sobel_magnitude(img: &GrayImage, x: u32, y: u32) -> u32 {
+  let (w, h) = img.dimensions();
+  let at = |ix: i64, iy: i64| -> i64 {
+    let cx = ix.clamp(0, w as i64 - 1) as u32;
+    let cy = iy.clamp(0, h as i64 - 1) as u32;
+    img.get_pixel(cx, cy).0[0] as i64
+  };
+  let (x, y) = (x as i64, y as i64);
+  let gx = at(x + 1, y - 1) + 2 * at(x + 1, y) + at(x + 1, y + 1)
+    - at(x - 1, y - 1)
+    - 2 * at(x - 1, y)
+    - at(x - 1, y + 1);
+  let gy = at(x - 1, y + 1) + 2 * at(x, y + 1) + at(x + 1, y + 1)
+    - at(x - 1, y - 1)
+    - 2 * at(x, y - 1)
+    - at(x + 1, y - 1);
+  ((gx * gx + gy * gy) as f64).sqrt() as u32
+}
+
+/// Builder holding the options used to turn an image into braille art.
+#[derive(Clone, Debug)]
+pub struct BrailleArt {
+  mode: BrailleArtMode,
+  scale: f32,
+  invert: bool,
+  auto_threshold: bool,
+  color: bool,
+}
+
+impl Default for BrailleArt {
+  fn default() -> Self {
+    BrailleArt {
+      mode: BrailleArtMode::default(),
+      scale: 1.0,
+      invert: false,
+      auto_threshold: false,
+      color: false,
+    }
+  }
+}
+
+impl BrailleArt {
+  /// Start from the default options (threshold 128, scale 1.0, no invert).
+  pub fn new() -> Self {
+    BrailleArt::default()
+  }
+
+  /// Select the mode that decides whether each dot is on.
+  pub fn mode(mut self, mode: BrailleArtMode) -> Self {
+    self.mode = mode;
+    self
+  }
+
+  /// Shortcut for [`BrailleArtMode::LumaThreshold`].
+  pub fn threshold(mut self, threshold: u8) -> Self {
+    self.mode = BrailleArtMode::LumaThreshold(threshold);
+    self
+  }
+
+  /// Resize factor applied before packing; `1.0` keeps the source size.
+  pub fn scale(mut self, scale: f32) -> Self {
+    self.scale = scale;
+    self
+  }
+
+  /// Flip every dot decision, swapping foreground and background.
+  pub fn invert(mut self, invert: bool) -> Self {
+    self.invert = invert;
+    self
+  }
+
+  /// Pick the luminance threshold automatically via Otsu's method instead of
+  /// using a fixed value. Only affects [`BrailleArtMode::LumaThreshold`].
+  pub fn auto_threshold(mut self, auto: bool) -> Self {
+    self.auto_threshold = auto;
+    self
+  }
+
+  /// Emit each glyph wrapped in a 24-bit ANSI truecolor escape whose RGB is
+  /// the mean color of the original pixels covered by that cell.
+  pub fn color(mut self, color: bool) -> Self {
+    self.color = color;
+    self
+  }
+
+  /// Render `img` to a [`BrailleCanvas`] using the configured options.
+  pub fn render(&self, img: DynamicImage) -> BrailleCanvas {
+    // When coloring, keep the original RGB around, resized to match the grid.
+    let rgb = self.color.then(|| {
+      let rgb = img.to_rgb8();
+      resize_like(&rgb, self.scale)
+    });
+    let gray = self.prepare(img);
+    let matrix = self.binarize(&gray);
+    let (w, h) = gray.dimensions();
+
+    let mut grid = Vec::with_capacity((h as usize).div_ceil(4));
+    let mut colors = rgb.is_some().then(Vec::new);
+    for y in (0..h).step_by(4) {
+      let mut line = Vec::with_capacity((w as usize).div_ceil(2));
+      let mut color_line = colors.is_some().then(Vec::new);
+      for x in (0..w).step_by(2) {
+        let mut dots: u8 = 0;
+        for dy in 0..4u32 {
+          for dx in 0..2u32 {
+            let (px, py) = (x + dx, y + dy);
+            if px < w && py < h && matrix[py as usize][px as usize] {
+              dots |= 1 << cell_bit(dx, dy);
+            }
+          }
+        }
+        line.push(cell_to_char(dots));
+        if let (Some(rgb), Some(color_line)) = (&rgb, &mut color_line) {
+          color_line.push(cell_mean_color(rgb, x, y));
+        }
+      }
+      grid.push(line);
+      if let (Some(colors), Some(color_line)) = (&mut colors, color_line) {
+        colors.push(color_line);
+      }
+    }
+
+    BrailleCanvas { grid, colors }
+  }
+
+  /// Resolve the configured mode into a boolean dot matrix, applying invert.
+  ///
+  /// Per-pixel modes defer to [`BrailleArtMode::is_on`]; [`BrailleArtMode::Dither`]
+  /// needs the whole image in scan order, so it runs Floyd–Steinberg first.
+  fn binarize(&self, gray: &GrayImage) -> Vec<Vec<bool>> {
+    let (w, h) = gray.dimensions();
+    // Otsu overrides the fixed threshold when a luma mode is in effect.
+    let mode = match self.mode {
+      BrailleArtMode::LumaThreshold(_) if self.auto_threshold => {
+        BrailleArtMode::LumaThreshold(otsu_threshold(gray))
+      }
+      ref other => other.clone(),
+    };
+    let mut matrix = match mode {
+      BrailleArtMode::Dither => floyd_steinberg(gray),
+      _ => {
+        let mut rows = Vec::with_capacity(h as usize);
+        for y in 0..h {
+          let mut row = Vec::with_capacity(w as usize);
+          for x in 0..w {
+            row.push(mode.is_on(gray, x, y));
+          }
+          rows.push(row);
+        }
+        rows
+      }
+    };
+    if self.invert {
+      for row in &mut matrix {
+        for dot in row {
+          *dot = !*dot;
+        }
+      }
+    }
+    matrix
+  }
+
+  /// Convert to grayscale and apply the scale factor.
+  fn prepare(&self, img: DynamicImage) -> GrayImage {
+    prepare_luma(img, self.scale)
+  }
+}
+
+/// Resolve the [`BrailleArt::scale`] factor that makes an image of size
+/// `(img_w, img_h)` pixels fit a `(cols, rows)` character grid, given that each
+/// output cell covers `(cell_w, cell_h)` source pixels — 2×4 for braille, 1×2
+/// for the ASCII ramp. Aspect ratio is preserved by taking the smaller of the
+/// two axis factors.
+pub fn fit_scale(
+  (img_w, img_h): (u32, u32),
+  (cols, rows): (u16, u16),
+  (cell_w, cell_h): (u32, u32),
+) -> f32 {
+  if img_w == 0 || img_h == 0 {
+    return 1.0;
+  }
+  let fit_w = (cols as f32 * cell_w as f32) / img_w as f32;
+  let fit_h = (rows as f32 * cell_h as f32) / img_h as f32;
+  fit_w.min(fit_h)
+}
+
+/// Resize an RGB buffer by `scale`, mirroring [`prepare_luma`] so the color
+/// buffer lines up pixel-for-pixel with the grayscale one.
+fn resize_like(rgb: &RgbImage, scale: f32) -> RgbImage {
+  let (w, h) = rgb.dimensions();
+  if (scale - 1.0).abs() > f32::EPSILON {
+    let new_w = ((w as f32 * scale) as u32).max(1);
+    let new_h = ((h as f32 * scale) as u32).max(1);
+    image::imageops::resize(rgb, new_w, new_h, image::imageops::FilterType::Lanczos3)
+  } else {
+    rgb.clone()
+  }
+}
+
+/// Mean color of the 2×4 block of `rgb` whose top-left pixel is `(x, y)`,
+/// clamped to the image bounds.
+fn cell_mean_color(rgb: &RgbImage, x: u32, y: u32) -> [u8; 3] {
+  let (w, h) = rgb.dimensions();
+  let (mut r, mut g, mut b, mut count) = (0u32, 0u32, 0u32, 0u32);
+  for dy in 0..4u32 {
+    for dx in 0..2u32 {
+      let (px, py) = (x + dx, y + dy);
+      if px < w && py < h {
+        let p = rgb.get_pixel(px, py).0;
+        r += p[0] as u32;
+        g += p[1] as u32;
+        b += p[2] as u32;
+        count += 1;
+      }
+    }
+  }
+  let count = count.max(1);
+  [(r / count) as u8, (g / count) as u8, (b / count) as u8]
+}
+
+/// Convert `img` to grayscale and apply `scale` (keeping at least one pixel).
+fn prepare_luma(img: DynamicImage, scale: f32) -> GrayImage {
+  let gray = img.to_luma8();
+  let (w, h) = gray.dimensions();
+  if (scale - 1.0).abs() > f32::EPSILON {
+    let new_w = ((w as f32 * scale) as u32).max(1);
+    let new_h = ((h as f32 * scale) as u32).max(1);
+    image::imageops::resize(&gray, new_w, new_h, image::imageops::FilterType::Lanczos3)
+  } else {
+    gray
+  }
+}
+
+/// Map a `(dx, dy)` offset inside a 2×4 cell to its braille bit index.
+fn cell_bit(dx: u32, dy: u32) -> u8 {
+  match (dx, dy) {
+    (0, 0) => 0,
+    (0, 1) => 1,
+    (0, 2) => 2,
+    (0, 3) => 6,
+    (1, 0) => 3,
+    (1, 1) => 4,
+    (1, 2) => 5,
+    (1, 3) => 7,
+    _ => unreachable!("cell offsets are bounded by the 2×4 loop"),
+  }
+}
+
+/// Coarse 10-character density ramp, ordered sparse-to-dense.
+pub const RAMP_SHORT: &str = " .:-=+*#%@";
+
+/// Finer 65-character density ramp, ordered sparse-to-dense.
+pub const RAMP_LONG: &str =
+  " .'`^\",;Il!i><~+_-?]}{1)(|\\/tfjrxuvczXYUJCLQ0Omwqpdbkhao*#M&8%B@$";
+
+/// Builder for the grayscale ASCII-ramp renderer, an alternative to braille
+/// that maps each sampled block's average luminance to a ramp character.
+///
+/// Unlike braille, an ASCII cell is a single character wide, so blocks are
+/// sized to the font aspect ratio (1 column × 2 rows) to keep the picture
+/// from stretching vertically.
+#[derive(Clone, Debug)]
+pub struct AsciiArt {
+  ramp: Vec<char>,
+  scale: f32,
+  invert: bool,
+}
+
+impl Default for AsciiArt {
+  fn default() -> Self {
+    AsciiArt {
+      ramp: RAMP_SHORT.chars().collect(),
+      scale: 1.0,
+      invert: false,
+    }
+  }
+}
+
+impl AsciiArt {
+  /// Start from the default options (short ramp, scale 1.0, no invert).
+  pub fn new() -> Self {
+    AsciiArt::default()
+  }
+
+  /// Use `ramp` (sparse-to-dense) as the density ramp; empty strings are
+  /// ignored in favor of the current ramp.
+  pub fn ramp(mut self, ramp: &str) -> Self {
+    let chars: Vec<char> = ramp.chars().collect();
+    if !chars.is_empty() {
+      self.ramp = chars;
+    }
+    self
+  }
+
+  /// Resize factor applied before sampling; `1.0` keeps the source size.
+  pub fn scale(mut self, scale: f32) -> Self {
+    self.scale = scale;
+    self
+  }
+
+  /// Walk the ramp in the opposite direction (light areas become dense).
+  pub fn invert(mut self, invert: bool) -> Self {
+    self.invert = invert;
+    self
+  }
+
+  /// Render `img` to an [`AsciiCanvas`] using the configured options.
+  pub fn render(&self, img: DynamicImage) -> AsciiCanvas {
+    let gray = prepare_luma(img, self.scale);
+    let (w, h) = gray.dimensions();
+    let last = self.ramp.len() - 1;
+
+    let mut grid = Vec::with_capacity((h as usize).div_ceil(2));
+    for y in (0..h).step_by(2) {
+      let mut line = Vec::with_capacity(w as usize);
+      for x in 0..w {
+        // Average the 1×2 block that maps onto this character cell.
+        let mut sum = 0u32;
+        let mut count = 0u32;
+        for dy in 0..2u32 {
+          let py = y + dy;
+          if py < h {
+            sum += gray.get_pixel(x, py).0[0] as u32;
+            count += 1;
+          }
+        }
+        let luma = (sum / count.max(1)) as usize;
+        // Default to darker→denser so the ramp matches the braille mode, which
+        // lights dots where luminance is low. `invert` walks the ramp the other
+        // way (bright areas become dense).
+        let mut idx = last - luma * last / 255;
+        if self.invert {
+          idx = last - idx;
+        }
+        line.push(self.ramp[idx]);
+      }
+      grid.push(line);
+    }
+
+    AsciiCanvas { grid }
+  }
+}
+
+/// The character grid produced by [`AsciiArt::render`]. Owns its characters and
+/// renders as text through its [`Display`](fmt::Display) implementation.
+#[derive(Clone, Debug)]
+pub struct AsciiCanvas {
+  grid: Vec<Vec<char>>,
+}
+
+impl AsciiCanvas {
+  /// The underlying `char` grid, one inner `Vec` per line of output.
+  pub fn grid(&self) -> &[Vec<char>] {
+    &self.grid
+  }
+}
+
+impl fmt::Display for AsciiCanvas {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for line in &self.grid {
+      for ch in line {
+        f.write_str(ch.encode_utf8(&mut [0; 4]))?;
+      }
+      f.write_char('\n')?;
+    }
+    Ok(())
+  }
+}
+
+/// The braille grid produced by [`BrailleArt::render`]. Owns its characters and
+/// renders as text through its [`Display`](fmt::Display) implementation.
+#[derive(Clone, Debug)]
+pub struct BrailleCanvas {
+  grid: Vec<Vec<char>>,
+  /// Per-cell mean RGB, present only when color output was requested.
+  colors: Option<Vec<Vec<[u8; 3]>>>,
+}
+
+impl BrailleCanvas {
+  /// The underlying `char` grid, one inner `Vec` per line of output.
+  pub fn grid(&self) -> &[Vec<char>] {
+    &self.grid
+  }
+}
+
+impl fmt::Display for BrailleCanvas {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (y, line) in self.grid.iter().enumerate() {
+      for (x, ch) in line.iter().enumerate() {
+        match &self.colors {
+          Some(colors) => {
+            let [r, g, b] = colors[y][x];
+            write!(f, "\x1b[38;2;{r};{g};{b}m")?;
+            f.write_str(ch.encode_utf8(&mut [0; 4]))?;
+            f.write_str("\x1b[0m")?;
+          }
+          None => f.write_str(ch.encode_utf8(&mut [0; 4]))?,
+        }
+      }
+      f.write_char('\n')?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use image::GrayImage;
+
+  #[test]
+  fn otsu_splits_a_bimodal_histogram() {
+    // Two well-separated clusters at 50 and 200; the threshold must land
+    // between them.
+    let gray = GrayImage::from_raw(4, 2, vec![50, 50, 50, 50, 200, 200, 200, 200]).unwrap();
+    let t = otsu_threshold(&gray);
+    assert!((50..200).contains(&t), "threshold {t} did not separate the clusters");
+  }
+
+  #[test]
+  fn floyd_steinberg_quantizes_flat_images() {
+    let black = GrayImage::from_raw(2, 2, vec![0; 4]).unwrap();
+    assert_eq!(floyd_steinberg(&black), vec![vec![true, true], vec![true, true]]);
+
+    let white = GrayImage::from_raw(2, 2, vec![255; 4]).unwrap();
+    assert_eq!(floyd_steinberg(&white), vec![vec![false, false], vec![false, false]]);
+  }
+
+  #[test]
+  fn floyd_steinberg_diffuses_error_to_neighbors() {
+    // 130 quantizes up to 255 (err -125); the 7/16 share pushes the right
+    // neighbor from 160 down to ~105, which then quantizes to black (dot on).
+    let row = GrayImage::from_raw(2, 1, vec![130, 160]).unwrap();
+    assert_eq!(floyd_steinberg(&row), vec![vec![false, true]]);
+
+    // The same error, this time via the 5/16 downward weight.
+    let col = GrayImage::from_raw(1, 2, vec![130, 160]).unwrap();
+    assert_eq!(floyd_steinberg(&col), vec![vec![false], vec![true]]);
+  }
+
+  #[test]
+  fn floyd_steinberg_preserves_dimensions() {
+    let gray = GrayImage::from_raw(3, 5, vec![128; 15]).unwrap();
+    let matrix = floyd_steinberg(&gray);
+    assert_eq!(matrix.len(), 5);
+    assert!(matrix.iter().all(|row| row.len() == 3));
+  }
+
+  #[test]
+  fn fit_scale_preserves_aspect_by_taking_the_tighter_axis() {
+    // Braille cells (2×4). Square image, grid taller than wide in pixels →
+    // width-bound.
+    assert_eq!(fit_scale((100, 100), (50, 50), (2, 4)), 1.0);
+    // Wide image → still width-bound, at half scale.
+    assert_eq!(fit_scale((200, 100), (50, 50), (2, 4)), 0.5);
+    // ASCII cells (1×2) pack fewer pixels per glyph, so the same grid fits a
+    // smaller image.
+    assert_eq!(fit_scale((100, 100), (50, 50), (1, 2)), 0.5);
+    // Degenerate sizes fall back to 1.0.
+    assert_eq!(fit_scale((0, 0), (50, 50), (2, 4)), 1.0);
+  }
+}
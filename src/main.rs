@@ -1,110 +1,89 @@
-use image::{GenericImageView, GrayImage, Pixel};
+use image::GenericImageView;
+use noir_ascii_art::{fit_scale, AsciiArt, BrailleArt, BrailleArtMode, RAMP_LONG, RAMP_SHORT};
 use std::env;
-use std::fs::File;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
+use terminal_size::{terminal_size, Height, Width};
 
-/// Braille Unicode characters map 2×4 dot cells to code points 0x2800‑0x28FF.
-/// The bits are ordered as:
-/// 0 3
-/// 1 4
-/// 2 5
-/// 6 7
-fn cell_to_char(dots: u8) -> char {
-  // Unicode Braille pattern offset
-  std::char::from_u32(0x2800 + dots as u32).unwrap_or(' ')
-}
-
-/// Convert a grayscale image to a binary (black/white) matrix.
-/// `threshold` is the luminance value (0‑255) above which a pixel is considered white.
-fn binarize(img: &GrayImage, threshold: u8) -> Vec<Vec<bool>> {
-  let (w, h) = img.dimensions();
-  let mut rows = Vec::with_capacity(h as usize);
-  for y in 0..h {
-    let mut row = Vec::with_capacity(w as usize);
-    for x in 0..w {
-      let luma = img.get_pixel(x, y).0[0];
-      row.push(luma < threshold);
-    }
-    rows.push(row);
-  }
-  rows
-}
-
-/// Render the binary matrix as braille art.
-/// Each braille cell covers 2 columns × 4 rows of pixels.
-fn render_braille(matrix: &[Vec<bool>]) -> String {
-  let height = matrix.len();
-  let width = matrix[0].len();
-
-  let mut output = String::new();
+fn main() -> io::Result<()> {
+  // Expect: cargo run -- <path> [threshold] [scale] [--ascii] [--ramp NAME|STR] [--dither] [--auto-threshold] [--color] [--fit]
+  let args: Vec<String> = env::args().collect();
+  let mut positional: Vec<&String> = Vec::new();
+  let mut ascii = false;
+  let mut dither = false;
+  let mut auto_threshold = false;
+  let mut color = false;
+  let mut no_color = false;
+  let mut fit = false;
+  let mut ramp: Option<String> = None;
 
-  // step through the image in 4‑pixel‑high blocks
-  for y in (0..height).step_by(4) {
-    // each line of output corresponds to a row of braille cells
-    for x in (0..width).step_by(2) {
-      let mut dots: u8 = 0;
-      for dy in 0..4 {
-        for dx in 0..2 {
-          let py = y + dy;
-          let px = x + dx;
-          if py < height && px < width && matrix[py][px] {
-            // map (dx,dy) to the correct bit index
-            let bit = match (dx, dy) {
-              (0, 0) => 0,
-              (0, 1) => 1,
-              (0, 2) => 2,
-              (0, 3) => 6,
-              (1, 0) => 3,
-              (1, 1) => 4,
-              (1, 2) => 5,
-              (1, 3) => 7,
-              _ => unreachable!(),
-            };
-            dots |= 1 << bit;
-          }
-        }
+  let mut i = 1;
+  while i < args.len() {
+    match args[i].as_str() {
+      "--ascii" => ascii = true,
+      "--dither" => dither = true,
+      "--auto-threshold" => auto_threshold = true,
+      "--color" => color = true,
+      "--no-color" => no_color = true,
+      "--fit" => fit = true,
+      "--ramp" => {
+        i += 1;
+        ramp = args.get(i).cloned();
       }
-      output.push(cell_to_char(dots));
+      _ => positional.push(&args[i]),
     }
-    output.push('\n');
+    i += 1;
   }
-  output
-}
 
-fn main() -> io::Result<()> {
-  // Expect: cargo run -- <path> [threshold] [scale]
-  let args: Vec<String> = env::args().collect();
-  if args.len() < 2 {
+  if positional.is_empty() {
     eprintln!(
-      "Usage: {} <image> [threshold 0‑255] [scale 0.1‑10.0]",
+      "Usage: {} <image> [threshold 0‑255] [scale 0.1‑10.0] [--ascii] [--ramp short|long|STRING] [--dither] [--auto-threshold] [--color] [--fit]",
       args[0]
     );
     std::process::exit(1);
   }
 
-  let path = &args[1];
-  let threshold: u8 = args.get(2).and_then(|s| s.parse().ok()).unwrap_or(128);
-  let scale: f32 = args.get(3).and_then(|s| s.parse().ok()).unwrap_or(1.0);
+  let path = positional[0];
+  let threshold: u8 = positional.get(1).and_then(|s| s.parse().ok()).unwrap_or(128);
+  let scale: f32 = positional.get(2).and_then(|s| s.parse().ok()).unwrap_or(1.0);
 
-  // Load image, convert to grayscale, optionally resize
-  let img = image::open(path)
-    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
-    .to_luma8();
-  let (w, h) = img.dimensions();
-  let resized = if (scale - 1.0).abs() > f32::EPSILON {
-    let new_w = (w as f32 * scale) as u32;
-    let new_h = (h as f32 * scale) as u32;
-    image::imageops::resize(&img, new_w, new_h, image::imageops::FilterType::Lanczos3)
-  } else {
-    img
-  };
+  // Load image and hand it to the library; the CLI stays a thin wrapper.
+  let img = image::open(path).map_err(io::Error::other)?;
 
-  let binary = binarize(&resized, threshold);
-  let art = render_braille(&binary);
+  // `--fit` resolves the scale from the terminal grid, falling back to the
+  // explicit scale when the size is unavailable (e.g. piped output).
+  let scale = match (fit, terminal_size()) {
+    (true, Some((Width(cols), Height(rows)))) => {
+      // Braille glyphs cover 2×4 source pixels; ASCII ramp cells cover 1×2.
+      let cell = if ascii { (1, 2) } else { (2, 4) };
+      fit_scale(img.dimensions(), (cols, rows), cell)
+    }
+    _ => scale,
+  };
 
-  // Write to stdout (or a file if you prefer)
   let stdout = io::stdout();
+  // Color is opt-in and suppressed for non-TTY (piped) output or --no-color.
+  let use_color = color && !no_color && stdout.is_terminal();
   let mut handle = stdout.lock();
-  handle.write_all(art.as_bytes())?;
+
+  if ascii {
+    let ramp = match ramp.as_deref() {
+      Some("short") | None => RAMP_SHORT,
+      Some("long") => RAMP_LONG,
+      Some(custom) => custom,
+    };
+    let canvas = AsciiArt::new().ramp(ramp).scale(scale).render(img);
+    write!(handle, "{canvas}")?;
+  } else {
+    let mut builder = BrailleArt::new()
+      .threshold(threshold)
+      .scale(scale)
+      .auto_threshold(auto_threshold)
+      .color(use_color);
+    if dither {
+      builder = builder.mode(BrailleArtMode::Dither);
+    }
+    let canvas = builder.render(img);
+    write!(handle, "{canvas}")?;
+  }
   Ok(())
 }